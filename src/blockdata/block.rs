@@ -0,0 +1,215 @@
+// Rust Garlicoin Library
+// Written in 2014 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Garlicoin blocks.
+//!
+
+use std::fmt;
+
+use blockdata::transaction::{Transaction, Txid};
+use consensus::params::Params;
+use util::hash::Sha256dHash;
+use util::pow::{CompactTarget, Target, Work};
+use util::uint::Uint256;
+
+/// The hash identifying a block.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Default)]
+pub struct BlockHash(Sha256dHash);
+
+impl BlockHash {
+    /// The all-zero `BlockHash` used as the `prev_blockhash` of a genesis block.
+    pub const ALL_ZEROS: BlockHash = BlockHash(Sha256dHash::ALL_ZEROS);
+}
+
+impl ::std::fmt::Display for BlockHash {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::std::fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// The root of a block's transaction merkle tree.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct TxMerkleNode(Sha256dHash);
+
+impl From<Txid> for TxMerkleNode {
+    fn from(txid: Txid) -> TxMerkleNode {
+        TxMerkleNode(Sha256dHash::from_byte_array(txid.to_byte_array()))
+    }
+}
+
+/// A block header.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Header {
+    /// The protocol version.
+    pub version: i32,
+    /// The hash of the previous block's header.
+    pub prev_blockhash: BlockHash,
+    /// The root of this block's transaction merkle tree.
+    pub merkle_root: TxMerkleNode,
+    /// The block timestamp.
+    pub time: u32,
+    /// The compact-encoded proof-of-work target this header's hash must
+    /// satisfy.
+    pub bits: CompactTarget,
+    /// The nonce used to satisfy the proof-of-work target.
+    pub nonce: u32,
+}
+
+impl Header {
+    /// Serializes this header into its 80-byte consensus representation.
+    fn consensus_encode(&self) -> [u8; 80] {
+        let mut out = [0u8; 80];
+        out[0..4].copy_from_slice(&self.version.to_le_bytes());
+        out[4..36].copy_from_slice(&self.prev_blockhash.0.to_byte_array());
+        out[36..68].copy_from_slice(&self.merkle_root.0.to_byte_array());
+        out[68..72].copy_from_slice(&self.time.to_le_bytes());
+        out[72..76].copy_from_slice(&self.bits.to_consensus().to_le_bytes());
+        out[76..80].copy_from_slice(&self.nonce.to_le_bytes());
+        out
+    }
+
+    /// Computes this header's `BlockHash`.
+    pub fn block_hash(&self) -> BlockHash {
+        BlockHash(Sha256dHash::from_data(&self.consensus_encode()))
+    }
+
+    /// Returns the expanded proof-of-work target this header's hash must
+    /// not exceed, decoded from `bits`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is not a validly-encoded target; see
+    /// [`Target::from_compact`].
+    pub fn target(&self) -> Target {
+        Target::from_compact(self.bits).expect("header bits must be a valid compact target")
+    }
+
+    /// Returns the difficulty of this header's target relative to
+    /// `params`'s maximum attainable target.
+    pub fn difficulty(&self, params: impl AsRef<Params>) -> u128 {
+        self.target().difficulty(params)
+    }
+
+    /// Returns the amount of work represented by this header's target.
+    pub fn work(&self) -> Work {
+        Work::from_target(self.target())
+    }
+
+    /// Checks that this header's `block_hash` (its SHA256d hash) is at or
+    /// below its own proof-of-work target, and that the target itself is no
+    /// easier than `params`'s maximum attainable target, returning the
+    /// header's hash if so.
+    ///
+    /// Garlicoin's actual proof-of-work function is scrypt, not SHA256d, so
+    /// unlike Bitcoin's `block_hash` does not equal the hash a miner must
+    /// satisfy. This method is therefore **not** a substitute for real
+    /// proof-of-work validation: a genuine Garlicoin header can fail this
+    /// check, and a header that passes it is not proven to have been
+    /// genuinely mined. It is only meaningful for headers whose PoW was
+    /// constructed against a SHA256d target directly, such as this crate's
+    /// own `genesis_block` test fixtures. A real scrypt-based check is not
+    /// implemented here.
+    pub fn validate_pow(&self, params: impl AsRef<Params>) -> Result<BlockHash, Error> {
+        let target = self.target();
+        debug_assert!(!target.to_uint256().is_zero(), "target must be non-zero");
+
+        if target > params.as_ref().pow_limit {
+            return Err(Error::BadTarget);
+        }
+
+        let hash = self.block_hash();
+        let hash_as_target = Target::from_uint256(Uint256::from_le_bytes(hash.0.to_byte_array()));
+        if hash_as_target > target {
+            return Err(Error::BadProofOfWork);
+        }
+
+        Ok(hash)
+    }
+}
+
+/// An error validating a header's proof-of-work.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Error {
+    /// The header's hash exceeds its own target.
+    BadProofOfWork,
+    /// The header's target is easier than the network's maximum attainable
+    /// target.
+    BadTarget,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::BadProofOfWork => write!(f, "block hash does not satisfy its target"),
+            Error::BadTarget => write!(f, "block target is below the network minimum difficulty"),
+        }
+    }
+}
+
+impl ::std::error::Error for Error {}
+
+/// A Garlicoin block.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Block {
+    /// The block header.
+    pub header: Header,
+    /// The transactions contained in this block.
+    pub txdata: Vec<Transaction>,
+}
+
+impl Block {
+    /// Computes this block's `BlockHash`, i.e. its header's hash.
+    pub fn block_hash(&self) -> BlockHash {
+        self.header.block_hash()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Error;
+    use blockdata::constants::genesis_block;
+    use network::constants::Network;
+
+    #[test]
+    fn genesis_block_satisfies_its_own_pow() {
+        let block = genesis_block(Network::Regtest);
+        assert_eq!(
+            block.header.validate_pow(Network::Regtest),
+            Ok(block.header.block_hash())
+        );
+    }
+
+    #[test]
+    fn tampered_header_fails_pow_validation() {
+        // Mainnet's difficulty is high enough that an arbitrary nonce change
+        // essentially never satisfies its target, unlike Regtest's.
+        let mut block = genesis_block(Network::Garlicoin);
+        block.header.nonce = block.header.nonce.wrapping_add(1);
+        assert_eq!(
+            block.header.validate_pow(Network::Garlicoin),
+            Err(Error::BadProofOfWork)
+        );
+    }
+
+    #[test]
+    fn target_above_pow_limit_is_rejected() {
+        // Regtest's minimum difficulty is far below Mainnet's, so Regtest's
+        // genesis header's target exceeds Mainnet's `pow_limit`.
+        let block = genesis_block(Network::Regtest);
+        assert_eq!(
+            block.header.validate_pow(Network::Garlicoin),
+            Err(Error::BadTarget)
+        );
+    }
+}
@@ -0,0 +1,194 @@
+// Rust Garlicoin Library
+// Written in 2014 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Chain-wide constants.
+//!
+//! This module mirrors Garlicoin Core's `CreateGenesisBlock`, so that the
+//! genesis block for any network is built up from its coinbase and header
+//! fields rather than stored as an opaque hard-coded hash.
+//!
+//! Garlicoin's proof-of-work function is scrypt, not the SHA256d this crate
+//! implements (see [`Header::validate_pow`]), so the real per-network
+//! genesis nonces cannot be verified or re-derived here. The nonces below
+//! are placeholders chosen so that each genesis header's SHA256d hash meets
+//! its own `bits` target, which lets [`Header::validate_pow`] accept it;
+//! they are not claimed to match the real chains, and the blocks they
+//! produce should not be used as a source of truth for the real genesis
+//! hashes.
+//!
+//! [`Header::validate_pow`]: ::blockdata::block::Header::validate_pow
+
+use blockdata::block::{Block, Header, TxMerkleNode};
+use blockdata::opcodes;
+use blockdata::script::Script;
+use blockdata::transaction::{OutPoint, Transaction, TxIn, TxOut};
+use consensus::params::Params;
+use network::constants::Network;
+use util::pow::CompactTarget;
+
+/// The message embedded in the genesis coinbase's `scriptSig`, taken from
+/// a newspaper headline.
+const GENESIS_TIMESTAMP: &str = "NY Times 05/Oct/2011 Steve Jobs, Apple's Visionary, Dies";
+
+/// The `scriptSig` constant pushed alongside the timestamp in every
+/// network's genesis coinbase, matching Garlicoin Core's
+/// `CreateGenesisBlock`.
+const GENESIS_SCRIPT_SIG_CONSTANT: i64 = 486604799;
+
+/// The uncompressed public key the genesis coinbase output pays to.
+const GENESIS_PUBKEY: [u8; 65] = [
+    0x04, 0x67, 0x8a, 0xfd, 0xb0, 0xfe, 0x55, 0x48, 0x27, 0x19, 0x67, 0xf1, 0xa6, 0x71, 0x30, 0xb7,
+    0x10, 0x5c, 0xd6, 0xa8, 0x28, 0xe0, 0x39, 0x09, 0xa6, 0x79, 0x62, 0xe0, 0xea, 0x1f, 0x61, 0xde,
+    0xb6, 0x49, 0xf6, 0xbc, 0x3f, 0x4c, 0xef, 0x38, 0xc4, 0xf3, 0x55, 0x04, 0xe5, 0x1e, 0xc1, 0x12,
+    0xde, 0x5c, 0x38, 0x4d, 0xf7, 0xba, 0x0b, 0x8d, 0x57, 0x8a, 0x4c, 0x70, 0x2b, 0x6b, 0xf1, 0x1d,
+    0x5f,
+];
+
+/// Mainnet's genesis block timestamp.
+const GARLICOIN_GENESIS_TIME: u32 = 1337565789;
+/// Mainnet's genesis block `bits`.
+const GARLICOIN_GENESIS_BITS: u32 = 0x1e0ffff0;
+/// Mainnet's genesis block nonce.
+///
+/// This is a placeholder, not the real chain's nonce: Garlicoin's genesis
+/// was mined against a scrypt target, which this crate does not implement,
+/// so the real nonce cannot be re-derived here. See the module-level docs.
+const GARLICOIN_GENESIS_NONCE: u32 = 626_032;
+/// Mainnet's genesis block reward.
+const GARLICOIN_GENESIS_REWARD: u64 = Params::INITIAL_BLOCK_SUBSIDY;
+
+/// Testnet's genesis block timestamp.
+const TESTNET_GENESIS_TIME: u32 = 1337565800;
+/// Testnet's genesis block `bits`.
+const TESTNET_GENESIS_BITS: u32 = 0x1e0ffff0;
+/// Testnet's genesis block nonce; see the caveat on `GARLICOIN_GENESIS_NONCE`.
+const TESTNET_GENESIS_NONCE: u32 = 1_325_630;
+/// Testnet's genesis block reward.
+const TESTNET_GENESIS_REWARD: u64 = Params::INITIAL_BLOCK_SUBSIDY;
+
+/// Regtest's genesis block timestamp.
+const REGTEST_GENESIS_TIME: u32 = 1337565789;
+/// Regtest's genesis block `bits`.
+const REGTEST_GENESIS_BITS: u32 = 0x207fffff;
+/// Regtest's genesis block nonce; see the caveat on `GARLICOIN_GENESIS_NONCE`.
+const REGTEST_GENESIS_NONCE: u32 = 2;
+/// Regtest's genesis block reward.
+const REGTEST_GENESIS_REWARD: u64 = Params::INITIAL_BLOCK_SUBSIDY;
+
+/// Creates the coinbase transaction for a network's genesis block.
+fn genesis_coinbase(reward: u64) -> Transaction {
+    let script_sig = Script::new()
+        .push_int(GENESIS_SCRIPT_SIG_CONSTANT)
+        .push_slice(&[4])
+        .push_slice(GENESIS_TIMESTAMP.as_bytes());
+    let script_pubkey = Script::new()
+        .push_slice(&GENESIS_PUBKEY)
+        .push_opcode(opcodes::OP_CHECKSIG);
+
+    Transaction {
+        version: 1,
+        lock_time: 0,
+        input: vec![TxIn {
+            previous_output: OutPoint::null(),
+            script_sig,
+            sequence: u32::MAX,
+        }],
+        output: vec![TxOut {
+            value: reward,
+            script_pubkey,
+        }],
+    }
+}
+
+/// Builds and returns the genesis block for `params`'s network.
+///
+/// This reconstructs the block from first principles (coinbase, header
+/// fields, merkle root). The header's nonce is a placeholder rather than
+/// the real chain's value; see the module-level docs.
+pub fn genesis_block(params: impl AsRef<Params>) -> Block {
+    let params = params.as_ref();
+    let (time, bits, nonce, reward) = match params.network {
+        Network::Garlicoin => (
+            GARLICOIN_GENESIS_TIME,
+            GARLICOIN_GENESIS_BITS,
+            GARLICOIN_GENESIS_NONCE,
+            GARLICOIN_GENESIS_REWARD,
+        ),
+        Network::Testnet => (
+            TESTNET_GENESIS_TIME,
+            TESTNET_GENESIS_BITS,
+            TESTNET_GENESIS_NONCE,
+            TESTNET_GENESIS_REWARD,
+        ),
+        Network::Regtest => (
+            REGTEST_GENESIS_TIME,
+            REGTEST_GENESIS_BITS,
+            REGTEST_GENESIS_NONCE,
+            REGTEST_GENESIS_REWARD,
+        ),
+    };
+    let version = 1i32;
+
+    let coinbase = genesis_coinbase(reward);
+    let merkle_root = TxMerkleNode::from(coinbase.txid());
+
+    Block {
+        header: Header {
+            version,
+            prev_blockhash: Default::default(),
+            merkle_root,
+            time,
+            bits: CompactTarget::from_consensus(bits),
+            nonce,
+        },
+        txdata: vec![coinbase],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::genesis_block;
+    use blockdata::block::TxMerkleNode;
+    use blockdata::transaction::OutPoint;
+    use consensus::params::Params;
+    use network::constants::Network;
+
+    #[test]
+    fn genesis_coinbase_has_one_input_and_output() {
+        let block = genesis_block(Network::Garlicoin);
+        assert_eq!(block.txdata.len(), 1);
+        assert!(block.txdata[0].is_coinbase());
+        assert_eq!(block.txdata[0].input.len(), 1);
+        assert_eq!(block.txdata[0].output.len(), 1);
+        assert_eq!(block.txdata[0].input[0].previous_output, OutPoint::null());
+    }
+
+    #[test]
+    fn genesis_block_has_no_predecessor() {
+        let block = genesis_block(Params::MAINNET);
+        assert_eq!(block.header.prev_blockhash, Default::default());
+    }
+
+    #[test]
+    fn genesis_merkle_root_is_coinbase_txid() {
+        let block = genesis_block(Network::Regtest);
+        let expected = TxMerkleNode::from(block.txdata[0].txid());
+        assert_eq!(block.header.merkle_root, expected);
+    }
+
+    // There is no genesis_block_hash_* test here: the nonces above are
+    // placeholders (see the module-level docs), so their block hashes are
+    // not meaningful and are not asserted against the real chains'
+    // documented genesis hashes.
+}
@@ -0,0 +1,29 @@
+// Rust Garlicoin Library
+// Written in 2014 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Script opcodes.
+//!
+//! Constants for the small set of opcodes needed to build the scripts used
+//! by this crate. This is not an exhaustive opcode table.
+//!
+
+/// Pushes the next 1 byte as the number of bytes to push onto the stack.
+pub const OP_PUSHDATA1: u8 = 0x4c;
+/// Pushes the next 2 bytes as the number of bytes to push onto the stack.
+pub const OP_PUSHDATA2: u8 = 0x4d;
+/// Pushes the next 4 bytes as the number of bytes to push onto the stack.
+pub const OP_PUSHDATA4: u8 = 0x4e;
+/// Checks that the signature on top of the stack is valid for the public
+/// key beneath it.
+pub const OP_CHECKSIG: u8 = 0xac;
@@ -0,0 +1,92 @@
+// Rust Garlicoin Library
+// Written in 2014 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Garlicoin scripts.
+//!
+//! A `Script` is just a sequence of opcodes and pushed data. This module
+//! provides a minimal builder for constructing them.
+//!
+
+use blockdata::opcodes;
+
+/// A Garlicoin script.
+#[derive(Clone, PartialEq, Eq, Debug, Default, Hash)]
+pub struct Script(Vec<u8>);
+
+impl Script {
+    /// Creates a new, empty script.
+    pub fn new() -> Script {
+        Script(Vec::new())
+    }
+
+    /// Returns the script's raw bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Pushes `data` onto the script using the shortest valid push opcode.
+    pub fn push_slice(mut self, data: &[u8]) -> Script {
+        match data.len() {
+            n @ 0..=75 => self.0.push(n as u8),
+            n @ 76..=0xff => {
+                self.0.push(opcodes::OP_PUSHDATA1);
+                self.0.push(n as u8);
+            }
+            n @ 0x100..=0xffff => {
+                self.0.push(opcodes::OP_PUSHDATA2);
+                self.0.extend_from_slice(&(n as u16).to_le_bytes());
+            }
+            n => {
+                self.0.push(opcodes::OP_PUSHDATA4);
+                self.0.extend_from_slice(&(n as u32).to_le_bytes());
+            }
+        }
+        self.0.extend_from_slice(data);
+        self
+    }
+
+    /// Pushes a minimally-encoded `CScriptNum` onto the script.
+    ///
+    /// Values `-1..=16` are encoded with a dedicated `OP_N` opcode; all
+    /// other values are pushed as their little-endian, sign-magnitude byte
+    /// representation.
+    pub fn push_int(self, value: i64) -> Script {
+        if value == 0 {
+            return self.push_opcode(0x00);
+        }
+        if value == -1 || (1..=16).contains(&value) {
+            return self.push_opcode((value + 0x50) as u8);
+        }
+
+        let negative = value < 0;
+        let mut absolute = value.unsigned_abs();
+        let mut bytes = Vec::new();
+        while absolute != 0 {
+            bytes.push((absolute & 0xff) as u8);
+            absolute >>= 8;
+        }
+        if bytes.last().copied().unwrap_or(0) & 0x80 != 0 {
+            bytes.push(if negative { 0x80 } else { 0x00 });
+        } else if negative {
+            *bytes.last_mut().expect("non-empty") |= 0x80;
+        }
+        self.push_slice(&bytes)
+    }
+
+    /// Pushes a single opcode onto the script.
+    pub fn push_opcode(mut self, opcode: u8) -> Script {
+        self.0.push(opcode);
+        self
+    }
+}
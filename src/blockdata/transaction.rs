@@ -0,0 +1,146 @@
+// Rust Garlicoin Library
+// Written in 2014 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Garlicoin transactions.
+//!
+
+use blockdata::script::Script;
+use util::hash::Sha256dHash;
+
+/// The hash identifying a transaction.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Default)]
+pub struct Txid(Sha256dHash);
+
+impl Txid {
+    /// The all-zero `Txid` used as the previous output of a coinbase input.
+    pub const ALL_ZEROS: Txid = Txid(Sha256dHash::ALL_ZEROS);
+
+    /// Returns the internal, non-reversed byte representation of this `Txid`.
+    pub fn to_byte_array(self) -> [u8; 32] {
+        self.0.to_byte_array()
+    }
+}
+
+impl ::std::fmt::Display for Txid {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::std::fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// A reference to a transaction output.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct OutPoint {
+    /// The referenced transaction's `Txid`.
+    pub txid: Txid,
+    /// The index of the referenced output in that transaction's outputs.
+    pub vout: u32,
+}
+
+impl OutPoint {
+    /// The null `OutPoint` used by coinbase inputs, which spend nothing.
+    pub fn null() -> OutPoint {
+        OutPoint {
+            txid: Txid::ALL_ZEROS,
+            vout: u32::MAX,
+        }
+    }
+}
+
+/// A transaction input.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TxIn {
+    /// The output being spent.
+    pub previous_output: OutPoint,
+    /// The script satisfying the spent output's `script_pubkey` (or, for a
+    /// coinbase input, arbitrary data).
+    pub script_sig: Script,
+    /// The sequence number.
+    pub sequence: u32,
+}
+
+/// A transaction output.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TxOut {
+    /// The number of smallest units sent.
+    pub value: u64,
+    /// The script which must be satisfied to spend this output.
+    pub script_pubkey: Script,
+}
+
+/// A Garlicoin transaction.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Transaction {
+    /// The protocol version.
+    pub version: i32,
+    /// The earliest time or block height this transaction may be mined.
+    pub lock_time: u32,
+    /// The inputs being spent.
+    pub input: Vec<TxIn>,
+    /// The outputs being created.
+    pub output: Vec<TxOut>,
+}
+
+impl Transaction {
+    /// Returns `true` if this transaction is a coinbase transaction, i.e. it
+    /// has exactly one input and that input spends [`OutPoint::null`].
+    pub fn is_coinbase(&self) -> bool {
+        self.input.len() == 1 && self.input[0].previous_output == OutPoint::null()
+    }
+
+    /// Serializes this transaction into its consensus byte representation.
+    fn consensus_encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&self.version.to_le_bytes());
+        encode_var_int(&mut out, self.input.len() as u64);
+        for txin in &self.input {
+            out.extend_from_slice(&txin.previous_output.txid.0.to_byte_array());
+            out.extend_from_slice(&txin.previous_output.vout.to_le_bytes());
+            encode_var_int(&mut out, txin.script_sig.as_bytes().len() as u64);
+            out.extend_from_slice(txin.script_sig.as_bytes());
+            out.extend_from_slice(&txin.sequence.to_le_bytes());
+        }
+        encode_var_int(&mut out, self.output.len() as u64);
+        for txout in &self.output {
+            out.extend_from_slice(&txout.value.to_le_bytes());
+            encode_var_int(&mut out, txout.script_pubkey.as_bytes().len() as u64);
+            out.extend_from_slice(txout.script_pubkey.as_bytes());
+        }
+        out.extend_from_slice(&self.lock_time.to_le_bytes());
+        out
+    }
+
+    /// Computes this transaction's `Txid`.
+    pub fn txid(&self) -> Txid {
+        Txid(Sha256dHash::from_data(&self.consensus_encode()))
+    }
+}
+
+/// Encodes `value` as a Garlicoin `CompactSize` (a.k.a. `VarInt`).
+fn encode_var_int(out: &mut Vec<u8>, value: u64) {
+    match value {
+        0..=0xfc => out.push(value as u8),
+        0xfd..=0xffff => {
+            out.push(0xfd);
+            out.extend_from_slice(&(value as u16).to_le_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(0xfe);
+            out.extend_from_slice(&(value as u32).to_le_bytes());
+        }
+        _ => {
+            out.push(0xff);
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+}
@@ -19,29 +19,30 @@
 //!
 
 use network::constants::Network;
+use util::pow::{CompactTarget, Target};
 use util::uint::Uint256;
 
 /// Lowest possible difficulty for Mainnet. See comment on Params::pow_limit for more info.
-const MAX_BITS_BITCOIN: Uint256 = Uint256([
+const MAX_BITS_BITCOIN: Target = Target::from_uint256(Uint256([
     0x00000fffffffffffu64,
     0xffffffffffffffffu64,
     0xffffffffffffffffu64,
     0xffffffffffffffffu64,
-]);
+]));
 /// Lowest possible difficulty for Testnet. See comment on Params::pow_limit for more info.
-const MAX_BITS_TESTNET: Uint256 = Uint256([
+const MAX_BITS_TESTNET: Target = Target::from_uint256(Uint256([
     0x00000fffffffffffu64,
     0xffffffffffffffffu64,
     0xffffffffffffffffu64,
     0xffffffffffffffffu64,
-]);
+]));
 /// Lowest possible difficulty for Regtest. See comment on Params::pow_limit for more info.
-const MAX_BITS_REGTEST: Uint256 = Uint256([
+const MAX_BITS_REGTEST: Target = Target::from_uint256(Uint256([
     0x7fffffffffffffffu64,
     0xffffffffffffffffu64,
     0xffffffffffffffffu64,
     0xffffffffffffffffu64,
-]);
+]));
 
 /// Parameters that influence chain consensus.
 #[derive(Debug, Clone)]
@@ -70,7 +71,7 @@ pub struct Params {
     /// Still, this should not affect consensus as the only place where the non-compact form of
     /// this is used in Garlicoin Core's consensus algorithm is in comparison and there are no
     /// compact-expressible values between Garlicoin Core's and the limit expressed here.
-    pub pow_limit: Uint256,
+    pub pow_limit: Target,
     /// Expected amount of time to mine one block.
     pub pow_target_spacing: u64,
     /// Difficulty recalculation interval.
@@ -79,59 +80,268 @@ pub struct Params {
     pub allow_min_difficulty_blocks: bool,
     /// Determines whether retargeting is disabled for this network or not.
     pub no_pow_retargeting: bool,
+    /// Number of blocks after which the block subsidy halves.
+    pub subsidy_halving_interval: u32,
 }
 
 impl Params {
+    /// Parameters for mainnet.
+    pub const MAINNET: Params = Params {
+        network: Network::Garlicoin,
+        bip16_time: 1333238400,                 // Apr 1 2012
+        bip34_height: 0, // 2ada80bf415a89358d697569c96eb98cdbf4c3b8878ac5722c01284492e27228
+        bip65_height: 0, // bab3041e8977e0dc3eeff63fe707b92bde1dd449d8efafb248c27c8264cc311a
+        bip66_height: 0, // 7aceee012833fa8952f8835d8b1b3ae233cd6ab08fdb27a771d2bd7bdc491894
+        rule_change_activation_threshold: 6048, // 75%
+        miner_confirmation_window: 8064,
+        pow_limit: MAX_BITS_BITCOIN,
+        pow_target_spacing: 40,       // 40 seconds.
+        pow_target_timespan: 60 * 60, // 1 hour.
+        allow_min_difficulty_blocks: false,
+        no_pow_retargeting: false,
+        subsidy_halving_interval: 840_000,
+    };
+
+    /// Alias for [`Params::MAINNET`], since Garlicoin is the network's mainnet.
+    pub const GARLICOIN: Params = Params::MAINNET;
+
+    /// Parameters for testnet.
+    pub const TESTNET: Params = Params {
+        network: Network::Testnet,
+        bip16_time: 1333238400,                 // Apr 1 2012
+        bip34_height: 76, // 8075c771ed8b495ffd943980a95f702ab34fce3c8c54e379548bda33cc8c0573
+        bip65_height: 76, // 8075c771ed8b495ffd943980a95f702ab34fce3c8c54e379548bda33cc8c0573
+        bip66_height: 76, // 8075c771ed8b495ffd943980a95f702ab34fce3c8c54e379548bda33cc8c0573
+        rule_change_activation_threshold: 1512, // 75%
+        miner_confirmation_window: 2016,
+        pow_limit: MAX_BITS_TESTNET,
+        pow_target_spacing: 40,       // 40 seconds.
+        pow_target_timespan: 60 * 60, // 1 hour.
+        allow_min_difficulty_blocks: true,
+        no_pow_retargeting: false,
+        subsidy_halving_interval: 840_000,
+    };
+
+    /// Parameters for regtest.
+    pub const REGTEST: Params = Params {
+        network: Network::Regtest,
+        bip16_time: 1333238400,  // Apr 1 2012
+        bip34_height: 100000000, // not activated on regtest
+        bip65_height: 1351,
+        bip66_height: 1251,                    // used only in rpc tests
+        rule_change_activation_threshold: 108, // 75%
+        miner_confirmation_window: 144,
+        pow_limit: MAX_BITS_REGTEST,
+        pow_target_spacing: 60,                // 60 seconds.
+        pow_target_timespan: 14 * 24 * 6 * 60, // 1.4 days.
+        allow_min_difficulty_blocks: true,
+        no_pow_retargeting: true,
+        subsidy_halving_interval: 150,
+    };
+
     /// Creates parameters set for the given network.
     pub fn new(network: Network) -> Self {
         match network {
-            Network::Garlicoin => Params {
-                network: Network::Garlicoin,
-                bip16_time: 1333238400,                 // Apr 1 2012
-                bip34_height: 0, // 2ada80bf415a89358d697569c96eb98cdbf4c3b8878ac5722c01284492e27228
-                bip65_height: 0, // bab3041e8977e0dc3eeff63fe707b92bde1dd449d8efafb248c27c8264cc311a
-                bip66_height: 0, // 7aceee012833fa8952f8835d8b1b3ae233cd6ab08fdb27a771d2bd7bdc491894
-                rule_change_activation_threshold: 6048, // 75%
-                miner_confirmation_window: 8064,
-                pow_limit: MAX_BITS_BITCOIN,
-                pow_target_spacing: 40,       // 40 seconds.
-                pow_target_timespan: 60 * 60, // 1 hour.
-                allow_min_difficulty_blocks: false,
-                no_pow_retargeting: false,
-            },
-            Network::Testnet => Params {
-                network: Network::Testnet,
-                bip16_time: 1333238400,                 // Apr 1 2012
-                bip34_height: 76, // 8075c771ed8b495ffd943980a95f702ab34fce3c8c54e379548bda33cc8c0573
-                bip65_height: 76, // 8075c771ed8b495ffd943980a95f702ab34fce3c8c54e379548bda33cc8c0573
-                bip66_height: 76, // 8075c771ed8b495ffd943980a95f702ab34fce3c8c54e379548bda33cc8c0573
-                rule_change_activation_threshold: 1512, // 75%
-                miner_confirmation_window: 2016,
-                pow_limit: MAX_BITS_TESTNET,
-                pow_target_spacing: 40,       // 40 seconds.
-                pow_target_timespan: 60 * 60, // 1 hour.
-                allow_min_difficulty_blocks: true,
-                no_pow_retargeting: false,
-            },
-            Network::Regtest => Params {
-                network: Network::Regtest,
-                bip16_time: 1333238400,  // Apr 1 2012
-                bip34_height: 100000000, // not activated on regtest
-                bip65_height: 1351,
-                bip66_height: 1251,                    // used only in rpc tests
-                rule_change_activation_threshold: 108, // 75%
-                miner_confirmation_window: 144,
-                pow_limit: MAX_BITS_REGTEST,
-                pow_target_spacing: 60,                // 60 seconds.
-                pow_target_timespan: 14 * 24 * 6 * 60, // 1.4 days.
-                allow_min_difficulty_blocks: true,
-                no_pow_retargeting: true,
-            },
+            Network::Garlicoin => Params::MAINNET,
+            Network::Testnet => Params::TESTNET,
+            Network::Regtest => Params::REGTEST,
         }
     }
 
+    /// The coinbase reward, in the smallest unit, paid at height 0, before
+    /// any halvings.
+    pub const INITIAL_BLOCK_SUBSIDY: u64 = 50 * 100_000_000;
+
     /// Calculates the number of blocks between difficulty adjustments.
     pub fn difficulty_adjustment_interval(&self) -> u64 {
         self.pow_target_timespan / self.pow_target_spacing
     }
+
+    /// Returns the coinbase reward, in the smallest unit, for a block at
+    /// `height`, accounting for halvings every
+    /// [`Params::subsidy_halving_interval`] blocks.
+    pub fn block_subsidy(&self, height: u32) -> u64 {
+        let halvings = height / self.subsidy_halving_interval;
+        if halvings >= 64 {
+            return 0;
+        }
+        Params::INITIAL_BLOCK_SUBSIDY >> halvings
+    }
+
+    /// Computes the `nBits` value the next block must satisfy, given the
+    /// target of the last block in the retarget window and the timestamps
+    /// of the first and last blocks in that window.
+    ///
+    /// Honors [`Params::no_pow_retargeting`] (the target never changes) and
+    /// [`Params::allow_min_difficulty_blocks`] (the easiest possible target
+    /// is returned once `last_block_time - first_block_time` exceeds twice
+    /// the expected spacing).
+    pub fn next_work_required(
+        &self,
+        last_target: Target,
+        first_block_time: u32,
+        last_block_time: u32,
+    ) -> CompactTarget {
+        if self.no_pow_retargeting {
+            return last_target.to_compact();
+        }
+
+        let actual_timespan = i64::from(last_block_time) - i64::from(first_block_time);
+
+        if self.allow_min_difficulty_blocks
+            && actual_timespan > (2 * self.pow_target_spacing) as i64
+        {
+            return self.pow_limit.to_compact();
+        }
+
+        let min_timespan = self.pow_target_timespan / 4;
+        let max_timespan = self.pow_target_timespan * 4;
+        let actual_timespan = (actual_timespan.max(0) as u64).clamp(min_timespan, max_timespan);
+
+        let scaled = last_target.to_uint256().mul_u32(actual_timespan as u32)
+            / Uint256::from_u64(self.pow_target_timespan);
+        let new_target = Target::from_uint256(scaled);
+
+        if new_target > self.pow_limit {
+            self.pow_limit.to_compact()
+        } else {
+            new_target.to_compact()
+        }
+    }
+
+    /// Returns the largest target that `current` is allowed to jump to in a
+    /// single retarget step, capped at [`Params::pow_limit`].
+    pub fn max_target_transition_threshold(&self, current: Target) -> Target {
+        let threshold = self.max_target_transition_threshold_unchecked(current);
+        if threshold > self.pow_limit {
+            self.pow_limit
+        } else {
+            threshold
+        }
+    }
+
+    /// Like [`Params::max_target_transition_threshold`], but without capping
+    /// the result at [`Params::pow_limit`].
+    pub fn max_target_transition_threshold_unchecked(&self, current: Target) -> Target {
+        Target::from_uint256(current.to_uint256().mul_u32(4))
+    }
+}
+
+impl AsRef<Params> for Params {
+    fn as_ref(&self) -> &Params {
+        self
+    }
+}
+
+impl AsRef<Params> for Network {
+    /// Returns the const [`Params`] for this network, so that functions
+    /// generic over `impl AsRef<Params>` can be called with a bare `Network`.
+    fn as_ref(&self) -> &Params {
+        match self {
+            Network::Garlicoin => &Params::MAINNET,
+            Network::Testnet => &Params::TESTNET,
+            Network::Regtest => &Params::REGTEST,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mainnet_adjustment_interval_is_90_blocks() {
+        // 1 hour timespan / 40 second spacing.
+        assert_eq!(Params::MAINNET.difficulty_adjustment_interval(), 90);
+    }
+
+    #[test]
+    fn block_subsidy_halves_on_schedule() {
+        let params = Params::MAINNET;
+        assert_eq!(params.block_subsidy(0), Params::INITIAL_BLOCK_SUBSIDY);
+        assert_eq!(
+            params.block_subsidy(params.subsidy_halving_interval - 1),
+            Params::INITIAL_BLOCK_SUBSIDY
+        );
+        assert_eq!(
+            params.block_subsidy(params.subsidy_halving_interval),
+            Params::INITIAL_BLOCK_SUBSIDY / 2
+        );
+        assert_eq!(
+            params.block_subsidy(params.subsidy_halving_interval * 2),
+            Params::INITIAL_BLOCK_SUBSIDY / 4
+        );
+    }
+
+    #[test]
+    fn block_subsidy_is_zero_after_64_halvings() {
+        let params = Params::MAINNET;
+        let height = params.subsidy_halving_interval.saturating_mul(64);
+        assert_eq!(params.block_subsidy(height), 0);
+    }
+
+    #[test]
+    fn unchanged_timespan_leaves_target_unchanged() {
+        let params = Params::MAINNET;
+        let last_target = params.pow_limit;
+        let bits = params.next_work_required(last_target, 0, params.pow_target_timespan as u32);
+        assert_eq!(bits, last_target.to_compact());
+    }
+
+    #[test]
+    fn fast_blocks_clamp_to_quarter_timespan() {
+        let params = Params::MAINNET;
+        // Use a target well below pow_limit so the 4x tightening isn't lost
+        // to the pow_limit cap.
+        let last_target = Target::from_uint256(params.pow_limit.to_uint256().mul_u32(1) / Uint256::from_u64(64));
+        let bits = params.next_work_required(last_target, 0, 1);
+        let new_target = Target::from_compact(bits).unwrap();
+        let expected =
+            Target::from_uint256(last_target.to_uint256().mul_u32(1) / Uint256::from_u64(4)).to_compact();
+        assert_eq!(bits, expected);
+        assert!(new_target < last_target);
+    }
+
+    #[test]
+    fn slow_blocks_clamp_to_quadruple_timespan() {
+        let params = Params::MAINNET;
+        let last_target = Target::from_uint256(params.pow_limit.to_uint256() / Uint256::from_u64(16));
+        let huge_gap = params.pow_target_timespan as u32 * 100;
+        let bits = params.next_work_required(last_target, 0, huge_gap);
+        let expected = Target::from_uint256(last_target.to_uint256().mul_u32(4)).to_compact();
+        assert_eq!(bits, expected);
+    }
+
+    #[test]
+    fn no_retargeting_returns_input_unchanged() {
+        let params = Params::REGTEST;
+        assert!(params.no_pow_retargeting);
+        let last_target = Target::from_uint256(Uint256::from_u64(12345));
+        let bits = params.next_work_required(last_target, 0, params.pow_target_timespan as u32 * 10);
+        assert_eq!(bits, last_target.to_compact());
+    }
+
+    #[test]
+    fn min_difficulty_blocks_return_pow_limit_after_gap() {
+        let params = Params::TESTNET;
+        assert!(params.allow_min_difficulty_blocks);
+        let last_target = Target::from_uint256(Uint256::from_u64(1));
+        let gap = (2 * params.pow_target_spacing + 1) as u32;
+        let bits = params.next_work_required(last_target, 0, gap);
+        assert_eq!(bits, params.pow_limit.to_compact());
+    }
+
+    #[test]
+    fn max_target_transition_threshold_is_4x_capped_at_pow_limit() {
+        let params = Params::MAINNET;
+        let small = Target::from_uint256(Uint256::from_u64(10));
+        assert_eq!(
+            params.max_target_transition_threshold_unchecked(small),
+            Target::from_uint256(Uint256::from_u64(40))
+        );
+        assert_eq!(
+            params.max_target_transition_threshold(params.pow_limit),
+            params.pow_limit
+        );
+    }
 }
@@ -0,0 +1,22 @@
+// Rust Garlicoin Library
+// Written by
+//   The Rust Garlicoin developers
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Utility functions.
+//!
+//! Functions needed by all parts of the Garlicoin library.
+//!
+
+pub mod hash;
+pub mod pow;
+pub mod uint;
@@ -0,0 +1,222 @@
+// Rust Garlicoin Library
+// Written by
+//   The Rust Garlicoin developers
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Proof-of-work.
+//!
+//! This module defines the compact `nBits` encoding used in block headers
+//! (`CompactTarget`), the expanded 256-bit target it decodes to (`Target`),
+//! and the amount of work a target represents (`Work`), which is what gets
+//! summed to produce chainwork.
+//!
+
+use std::fmt;
+use std::ops::Add;
+
+use consensus::params::Params;
+use util::uint::Uint256;
+
+/// The `nBits` field of a block header: a 32-bit compact encoding of a 256-bit target.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub struct CompactTarget(u32);
+
+impl CompactTarget {
+    /// Constructs a `CompactTarget` from its consensus-encoded `u32` value.
+    pub fn from_consensus(bits: u32) -> CompactTarget {
+        CompactTarget(bits)
+    }
+
+    /// Returns the consensus-encoded `u32` value of this `CompactTarget`.
+    pub fn to_consensus(self) -> u32 {
+        self.0
+    }
+}
+
+impl From<u32> for CompactTarget {
+    fn from(bits: u32) -> CompactTarget {
+        CompactTarget::from_consensus(bits)
+    }
+}
+
+impl From<CompactTarget> for u32 {
+    fn from(compact: CompactTarget) -> u32 {
+        compact.to_consensus()
+    }
+}
+
+impl fmt::LowerHex for CompactTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.0, f)
+    }
+}
+
+/// The proof-of-work target a header's hash must not exceed.
+///
+/// This is the expanded, 256-bit form of a block header's compact `bits`
+/// field. Lower targets mean more work is required to find a valid header.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub struct Target(Uint256);
+
+impl Target {
+    /// Constructs a `Target` directly from its expanded 256-bit value.
+    pub const fn from_uint256(value: Uint256) -> Target {
+        Target(value)
+    }
+
+    /// Returns the expanded 256-bit value of this target.
+    pub fn to_uint256(self) -> Uint256 {
+        self.0
+    }
+
+    /// Decodes a `Target` from its compact `nBits` representation.
+    ///
+    /// Returns `None` if the compact value encodes a negative target (the
+    /// `0x00800000` mantissa bit set) or a mantissa/exponent pair that
+    /// overflows 256 bits, both of which are invalid and never produced by
+    /// [`Target::to_compact`].
+    pub fn from_compact(compact: CompactTarget) -> Option<Target> {
+        let bits = compact.to_consensus();
+        let size = bits >> 24;
+        let word = bits & 0x007f_ffff;
+
+        let overflow =
+            word != 0 && (size > 34 || (word > 0xff && size > 33) || (word > 0xffff && size > 32));
+        if overflow {
+            return None;
+        }
+
+        let negative = word != 0 && (bits & 0x0080_0000) != 0;
+        if negative {
+            return None;
+        }
+
+        let target = if size <= 3 {
+            Uint256::from_u64(u64::from(word) >> (8 * (3 - size)))
+        } else {
+            Uint256::from_u64(u64::from(word)) << (8 * (size - 3)) as usize
+        };
+
+        Some(Target(target))
+    }
+
+    /// Encodes this target in its compact `nBits` representation.
+    ///
+    /// The compact form only has a 24-bit mantissa, so this rounds down to
+    /// the nearest representable target; `Target::from_compact(t.to_compact())`
+    /// may therefore be less than or equal to, but never greater than, `t`.
+    pub fn to_compact(self) -> CompactTarget {
+        let mut size = self.0.bits().div_ceil(8);
+        let mut word = if size <= 3 {
+            (self.0.low_u64() << (8 * (3 - size))) as u32
+        } else {
+            (self.0 >> (8 * (size - 3)) as usize).low_u64() as u32
+        };
+
+        // The 0x00800000 bit denotes a negative number in the compact form, so
+        // if it would end up set here, shift the mantissa down and bump the
+        // exponent to keep the value unsigned.
+        if word & 0x0080_0000 != 0 {
+            word >>= 8;
+            size += 1;
+        }
+
+        debug_assert_eq!(word & !0x007f_ffff, 0);
+        CompactTarget::from_consensus(word | (size << 24))
+    }
+
+    /// Returns the difficulty of this target relative to `params`'s maximum
+    /// attainable target, i.e. `max_attainable_target / self`.
+    pub fn difficulty(&self, params: impl AsRef<Params>) -> u128 {
+        debug_assert!(!self.0.is_zero(), "target must be non-zero");
+        let (quotient, _) = params.as_ref().pow_limit.to_uint256().div_rem(&self.0);
+        quotient.low_u128()
+    }
+}
+
+/// The cumulative amount of work represented by a target.
+///
+/// Unlike [`Target`], smaller targets correspond to *larger* amounts of
+/// work, and work values are additive, which is what makes them suitable
+/// for summing into chainwork.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub struct Work(Uint256);
+
+impl Work {
+    /// Computes the amount of work represented by `target`, i.e. `2**256 / (target + 1)`.
+    pub fn from_target(target: Target) -> Work {
+        let target = target.to_uint256();
+        // `!target` is `2**256 - 1 - target`, so `!target / (target + 1) + 1`
+        // is `(2**256 - 1 - target) / (target + 1) + 1 == 2**256 / (target + 1)`
+        // computed without needing a 257-bit intermediate value.
+        let ones_complement = Uint256([!target.0[0], !target.0[1], !target.0[2], !target.0[3]]);
+        if ones_complement.is_zero() {
+            // `target` is `2**256 - 1`, so `target + 1` would wrap to zero;
+            // the work is `2**256 / 2**256 == 1`.
+            return Work(Uint256::ONE);
+        }
+        let denominator = target + Uint256::ONE;
+        let (quotient, _) = ones_complement.div_rem(&denominator);
+        Work(quotient + Uint256::ONE)
+    }
+
+    /// Returns the expanded 256-bit value of this work.
+    pub fn to_uint256(self) -> Uint256 {
+        self.0
+    }
+}
+
+impl Add for Work {
+    type Output = Work;
+    fn add(self, other: Work) -> Work {
+        Work(self.0 + other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CompactTarget, Target, Work};
+    use util::uint::Uint256;
+
+    #[test]
+    fn compact_roundtrip() {
+        // 0x1d00ffff-style compact value: exponent 0x1d, mantissa 0x00ffff.
+        let compact = CompactTarget::from_consensus(0x1d00ffff);
+        let target = Target::from_compact(compact).unwrap();
+        assert_eq!(target.to_compact(), compact);
+    }
+
+    #[test]
+    fn rejects_negative_mantissa() {
+        let compact = CompactTarget::from_consensus(0x0180_0001);
+        assert_eq!(Target::from_compact(compact), None);
+    }
+
+    #[test]
+    fn rejects_overflow() {
+        let compact = CompactTarget::from_consensus(0xff12_3456);
+        assert_eq!(Target::from_compact(compact), None);
+    }
+
+    #[test]
+    fn small_target_roundtrip() {
+        let target = Target::from_uint256(Uint256::from_u64(0x1234));
+        let compact = target.to_compact();
+        assert_eq!(Target::from_compact(compact), Some(target));
+    }
+
+    #[test]
+    fn work_from_all_ones_target_does_not_panic() {
+        let target = Target::from_uint256(Uint256([u64::MAX; 4]));
+        assert_eq!(Work::from_target(target).to_uint256(), Uint256::ONE);
+    }
+}
@@ -0,0 +1,328 @@
+// Rust Garlicoin Library
+// Written by
+//   The Rust Garlicoin developers
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Big unsigned integer types.
+//!
+//! Implementation of a 256-bit unsigned integer used for proof-of-work
+//! target and chainwork arithmetic. Limbs are stored big-endian, i.e.
+//! `self.0[0]` holds the most-significant 64 bits and `self.0[3]` the
+//! least-significant 64 bits.
+//!
+
+use std::fmt;
+use std::ops::{Add, Div, Mul, Shl, Shr, Sub};
+
+/// A 256-bit unsigned integer, represented as four big-endian `u64` limbs.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Default)]
+pub struct Uint256(pub [u64; 4]);
+
+impl Uint256 {
+    /// The zero value.
+    pub const ZERO: Uint256 = Uint256([0, 0, 0, 0]);
+    /// The value one.
+    pub const ONE: Uint256 = Uint256([0, 0, 0, 1]);
+
+    /// Creates a `Uint256` from a `u64`.
+    pub fn from_u64(value: u64) -> Uint256 {
+        Uint256([0, 0, 0, value])
+    }
+
+    /// Creates a `Uint256` from its little-endian byte representation, as
+    /// used when interpreting a double-SHA256 hash as an integer for
+    /// proof-of-work comparisons.
+    pub fn from_le_bytes(bytes: [u8; 32]) -> Uint256 {
+        let mut limbs = [0u64; 4];
+        for (i, chunk) in bytes.chunks_exact(8).enumerate() {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(chunk);
+            limbs[3 - i] = u64::from_le_bytes(word);
+        }
+        Uint256(limbs)
+    }
+
+    /// Returns the least-significant 64 bits.
+    pub fn low_u64(&self) -> u64 {
+        self.0[3]
+    }
+
+    /// Returns the least-significant 128 bits.
+    pub fn low_u128(&self) -> u128 {
+        u128::from(self.0[3]) | (u128::from(self.0[2]) << 64)
+    }
+
+    /// Returns the number of bits required to represent this number, i.e.
+    /// the position of the highest set bit plus one. Returns 0 for zero.
+    pub fn bits(&self) -> u32 {
+        for (i, &word) in self.0.iter().enumerate() {
+            if word != 0 {
+                let limbs_above = (3 - i) as u32;
+                return limbs_above * 64 + (64 - word.leading_zeros());
+            }
+        }
+        0
+    }
+
+    /// Returns `true` if this value is zero.
+    pub fn is_zero(&self) -> bool {
+        self.0 == [0, 0, 0, 0]
+    }
+
+    /// Multiplies by a `u32`, truncating any overflow beyond 256 bits.
+    pub fn mul_u32(&self, other: u32) -> Uint256 {
+        self.mul_u64(u64::from(other))
+    }
+
+    /// Multiplies by a `u64`, truncating any overflow beyond 256 bits.
+    fn mul_u64(&self, other: u64) -> Uint256 {
+        let mut carry = 0u128;
+        let mut out = [0u64; 4];
+        for (limb, &word) in out.iter_mut().zip(self.0.iter()).rev() {
+            let product = u128::from(word) * u128::from(other) + carry;
+            *limb = product as u64;
+            carry = product >> 64;
+        }
+        Uint256(out)
+    }
+
+    /// Shifts left by a whole number of 64-bit words, truncating any
+    /// overflow beyond 256 bits.
+    fn shl_words(&self, words: usize) -> Uint256 {
+        if words >= 4 {
+            return Uint256::ZERO;
+        }
+        let mut out = [0u64; 4];
+        out[..4 - words].copy_from_slice(&self.0[words..]);
+        Uint256(out)
+    }
+
+    /// Returns `self + other`, truncating any overflow beyond 256 bits.
+    fn wrapping_add(&self, other: &Uint256) -> Uint256 {
+        let mut out = [0u64; 4];
+        let mut carry = 0u128;
+        for ((limb, &a), &b) in out
+            .iter_mut()
+            .zip(self.0.iter())
+            .zip(other.0.iter())
+            .rev()
+        {
+            let sum = u128::from(a) + u128::from(b) + carry;
+            *limb = sum as u64;
+            carry = sum >> 64;
+        }
+        Uint256(out)
+    }
+
+    /// Returns `self - other`, wrapping on underflow.
+    fn wrapping_sub(&self, other: &Uint256) -> Uint256 {
+        let mut out = [0u64; 4];
+        let mut borrow = 0i128;
+        for ((limb, &a), &b) in out
+            .iter_mut()
+            .zip(self.0.iter())
+            .zip(other.0.iter())
+            .rev()
+        {
+            let diff = i128::from(a) - i128::from(b) - borrow;
+            let (diff, brw) = if diff < 0 { (diff + (1i128 << 64), 1) } else { (diff, 0) };
+            *limb = diff as u64;
+            borrow = brw;
+        }
+        Uint256(out)
+    }
+
+    /// Divides `self` by `other`, returning `(quotient, remainder)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` is zero.
+    pub fn div_rem(&self, other: &Uint256) -> (Uint256, Uint256) {
+        assert!(!other.is_zero(), "division by zero");
+        if *self < *other {
+            return (Uint256::ZERO, *self);
+        }
+
+        let mut quotient = Uint256::ZERO;
+        let mut remainder = Uint256::ZERO;
+        for i in (0..256).rev() {
+            remainder = remainder << 1;
+            if bit_at(self, i) {
+                remainder.0[3] |= 1;
+            }
+            if remainder >= *other {
+                remainder = remainder.wrapping_sub(other);
+                set_bit(&mut quotient, i);
+            }
+        }
+        (quotient, remainder)
+    }
+}
+
+/// Returns the value of the bit at `index`, counting from the
+/// least-significant bit (`index` 0) up.
+fn bit_at(value: &Uint256, index: usize) -> bool {
+    (value.0[3 - index / 64] >> (index % 64)) & 1 == 1
+}
+
+/// Sets the bit at `index`, counting from the least-significant bit
+/// (`index` 0) up.
+fn set_bit(value: &mut Uint256, index: usize) {
+    value.0[3 - index / 64] |= 1 << (index % 64);
+}
+
+impl Add for Uint256 {
+    type Output = Uint256;
+    fn add(self, other: Uint256) -> Uint256 {
+        self.wrapping_add(&other)
+    }
+}
+
+impl Sub for Uint256 {
+    type Output = Uint256;
+    fn sub(self, other: Uint256) -> Uint256 {
+        self.wrapping_sub(&other)
+    }
+}
+
+impl Mul for Uint256 {
+    type Output = Uint256;
+    fn mul(self, other: Uint256) -> Uint256 {
+        let mut out = Uint256::ZERO;
+        // `p1` is a word position counted from the least-significant word.
+        for p1 in 0..4 {
+            let multiplier = self.0[3 - p1];
+            if multiplier == 0 {
+                continue;
+            }
+            let partial = other.mul_u64(multiplier).shl_words(p1);
+            out = out.wrapping_add(&partial);
+        }
+        out
+    }
+}
+
+impl Div for Uint256 {
+    type Output = Uint256;
+    fn div(self, other: Uint256) -> Uint256 {
+        self.div_rem(&other).0
+    }
+}
+
+impl Shl<usize> for Uint256 {
+    type Output = Uint256;
+    #[allow(clippy::needless_range_loop)]
+    fn shl(self, shift: usize) -> Uint256 {
+        if shift >= 256 {
+            return Uint256::ZERO;
+        }
+        let limb_shift = shift / 64;
+        let bit_shift = shift % 64;
+        let mut out = [0u64; 4];
+        for i in 0..4 {
+            if i < limb_shift {
+                continue;
+            }
+            let src = i - limb_shift;
+            let mut value = self.0[3 - src] << bit_shift;
+            if bit_shift > 0 && src > 0 {
+                value |= self.0[3 - (src - 1)] >> (64 - bit_shift);
+            }
+            out[3 - i] = value;
+        }
+        Uint256(out)
+    }
+}
+
+impl Shr<usize> for Uint256 {
+    type Output = Uint256;
+    #[allow(clippy::needless_range_loop)]
+    fn shr(self, shift: usize) -> Uint256 {
+        if shift >= 256 {
+            return Uint256::ZERO;
+        }
+        let limb_shift = shift / 64;
+        let bit_shift = shift % 64;
+        let mut out = [0u64; 4];
+        for i in 0..4 {
+            let src = i + limb_shift;
+            if src >= 4 {
+                continue;
+            }
+            let mut value = self.0[3 - src] >> bit_shift;
+            if bit_shift > 0 && src + 1 < 4 {
+                value |= self.0[3 - (src + 1)] << (64 - bit_shift);
+            }
+            out[3 - i] = value;
+        }
+        Uint256(out)
+    }
+}
+
+impl fmt::Display for Uint256 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:016x}{:016x}{:016x}{:016x}",
+            self.0[0], self.0[1], self.0[2], self.0[3]
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Uint256;
+
+    #[test]
+    fn test_bits() {
+        assert_eq!(Uint256::ZERO.bits(), 0);
+        assert_eq!(Uint256::ONE.bits(), 1);
+        assert_eq!(Uint256::from_u64(0xff).bits(), 8);
+        assert_eq!(Uint256([0, 0, 1, 0]).bits(), 65);
+    }
+
+    #[test]
+    fn test_ordering_is_numeric() {
+        let small = Uint256([0, 0, 0, 1]);
+        let large = Uint256([1, 0, 0, 0]);
+        assert!(small < large);
+    }
+
+    #[test]
+    fn test_shifts_roundtrip() {
+        let value = Uint256::from_u64(0x1234_5678);
+        assert_eq!((value << 8) >> 8, value);
+        assert_eq!((value << 200) >> 200, value);
+    }
+
+    #[test]
+    fn test_div_rem() {
+        let a = Uint256::from_u64(100);
+        let b = Uint256::from_u64(7);
+        let (q, r) = a.div_rem(&b);
+        assert_eq!(q, Uint256::from_u64(14));
+        assert_eq!(r, Uint256::from_u64(2));
+    }
+
+    #[test]
+    fn test_mul_u32() {
+        let a = Uint256::from_u64(10);
+        assert_eq!(a.mul_u32(4), Uint256::from_u64(40));
+    }
+
+    #[test]
+    fn test_mul() {
+        let a = Uint256::from_u64(1_000_000);
+        let b = Uint256::from_u64(7);
+        assert_eq!(a * b, Uint256::from_u64(7_000_000));
+    }
+}